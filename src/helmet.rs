@@ -0,0 +1,88 @@
+use rocket::{
+    fairing::{Fairing, Info, Kind},
+    http::Header,
+    Request, Response,
+};
+
+/// A Helmet-style fairing that sets secure-by-default response headers on
+/// every response. Attach [`SecurityHeaders::default()`] as-is, or toggle an
+/// individual header off with `None` / override it via the builder methods.
+pub struct SecurityHeaders {
+    frame_options: Option<String>,
+    content_type_options: Option<String>,
+    referrer_policy: Option<String>,
+    strict_transport_security: Option<String>,
+    content_security_policy: Option<String>,
+}
+
+impl Default for SecurityHeaders {
+    fn default() -> Self {
+        Self {
+            frame_options: Some("DENY".into()),
+            content_type_options: Some("nosniff".into()),
+            referrer_policy: Some("strict-origin-when-cross-origin".into()),
+            strict_transport_security: Some("max-age=63072000; includeSubDomains".into()),
+            content_security_policy: Some("default-src 'self'".into()),
+        }
+    }
+}
+
+impl SecurityHeaders {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `X-Frame-Options`. Defaults to `DENY`.
+    pub fn frame_options(mut self, value: impl Into<Option<String>>) -> Self {
+        self.frame_options = value.into();
+        self
+    }
+
+    /// `X-Content-Type-Options`. Defaults to `nosniff`.
+    pub fn content_type_options(mut self, value: impl Into<Option<String>>) -> Self {
+        self.content_type_options = value.into();
+        self
+    }
+
+    /// `Referrer-Policy`. Defaults to `strict-origin-when-cross-origin`.
+    pub fn referrer_policy(mut self, value: impl Into<Option<String>>) -> Self {
+        self.referrer_policy = value.into();
+        self
+    }
+
+    /// `Strict-Transport-Security`. Defaults to `max-age=63072000; includeSubDomains`.
+    pub fn strict_transport_security(mut self, value: impl Into<Option<String>>) -> Self {
+        self.strict_transport_security = value.into();
+        self
+    }
+
+    /// `Content-Security-Policy`. Defaults to `default-src 'self'`.
+    pub fn content_security_policy(mut self, value: impl Into<Option<String>>) -> Self {
+        self.content_security_policy = value.into();
+        self
+    }
+}
+
+#[rocket::async_trait]
+impl Fairing for SecurityHeaders {
+    fn info(&self) -> Info {
+        Info {
+            name: "Security Headers",
+            kind: Kind::Response,
+        }
+    }
+
+    async fn on_response<'r>(&self, _req: &'r Request<'_>, res: &mut Response<'r>) {
+        for (name, value) in [
+            ("X-Frame-Options", &self.frame_options),
+            ("X-Content-Type-Options", &self.content_type_options),
+            ("Referrer-Policy", &self.referrer_policy),
+            ("Strict-Transport-Security", &self.strict_transport_security),
+            ("Content-Security-Policy", &self.content_security_policy),
+        ] {
+            if let Some(value) = value {
+                res.set_header(Header::new(name, value.clone()));
+            }
+        }
+    }
+}
@@ -0,0 +1,218 @@
+use std::{collections::HashMap, future::Future};
+
+use futures::future::BoxFuture;
+use rocket::{
+    data::ToByteUnit, post, response, response::Responder, serde::Deserialize, Request, State,
+};
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::errors::{ResultJson, ResultJsonExt};
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const SERVER_ERROR: i64 = -32000;
+
+/// A JSON-RPC 2.0 error object.
+#[derive(Debug, Clone, Serialize)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            data: None,
+        }
+    }
+
+    fn with_data(mut self, data: Value) -> Self {
+        self.data = Some(data);
+        self
+    }
+}
+
+type BoxedHandler = Box<dyn Fn(Value) -> BoxFuture<'static, Result<Value, RpcError>> + Send + Sync>;
+
+/// A registry mapping JSON-RPC method names to handlers, served by the
+/// [`rpc`] route.
+#[derive(Default)]
+pub struct RpcService {
+    methods: HashMap<String, BoxedHandler>,
+}
+
+impl RpcService {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register an `async` handler for `method`. `params` is deserialized
+    /// from the request's `params` member; the handler's `Ok`/`Err` are
+    /// routed through the crate's [`ResultJson`] success/failure convention
+    /// before being mapped to the spec's `result`/`error` members.
+    pub fn register<P, R, E, F, Fut>(mut self, method: impl Into<String>, handler: F) -> Self
+    where
+        F: Fn(P) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<R, E>> + Send + 'static,
+        P: for<'de> Deserialize<'de> + Send + 'static,
+        R: Serialize + 'static,
+        E: Serialize + 'static,
+    {
+        let boxed: BoxedHandler =
+            Box::new(
+                move |raw_params: Value| match serde_json::from_value::<P>(raw_params) {
+                    Ok(params) => {
+                        let fut = handler(params);
+                        Box::pin(async move {
+                            let outcome: ResultJson = match fut.await {
+                                Ok(result) => ResultJson::Success(result, None),
+                                Err(err) => ResultJson::Failure(err, None),
+                            };
+
+                            match outcome {
+                                Ok(value) => Ok(value.success.unwrap_or(Value::Null)),
+                                Err(value) => {
+                                    Err(RpcError::new(SERVER_ERROR, "handler returned an error")
+                                        .with_data(value.failure.unwrap_or(Value::Null)))
+                                }
+                            }
+                        }) as BoxFuture<'static, Result<Value, RpcError>>
+                    }
+                    Err(err) => {
+                        let message = err.to_string();
+                        Box::pin(async move { Err(RpcError::new(INVALID_PARAMS, message)) })
+                    }
+                },
+            );
+
+        self.methods.insert(method.into(), boxed);
+        self
+    }
+
+    /// Dispatch a single request or batch array, returning the JSON-RPC
+    /// response to send back — or `None` if the whole call was made up of
+    /// notifications (no `id`), which get no response element at all.
+    pub async fn handle(&self, request: Value) -> Option<Value> {
+        match request {
+            Value::Array(batch) if !batch.is_empty() => {
+                let mut responses = Vec::new();
+                for call in batch {
+                    if let Some(response) = self.handle_single(call).await {
+                        responses.push(response);
+                    }
+                }
+                (!responses.is_empty()).then_some(Value::Array(responses))
+            }
+            Value::Array(_) => Some(error_response(
+                Value::Null,
+                RpcError::new(INVALID_REQUEST, "batch must not be empty"),
+            )),
+            single => self.handle_single(single).await,
+        }
+    }
+
+    async fn handle_single(&self, call: Value) -> Option<Value> {
+        let call: RpcCall = match serde_json::from_value(call) {
+            Ok(call) => call,
+            Err(err) => {
+                return Some(error_response(
+                    Value::Null,
+                    RpcError::new(INVALID_REQUEST, err.to_string()),
+                ));
+            }
+        };
+
+        if call.jsonrpc != "2.0" {
+            return Some(error_response(
+                call.id.unwrap_or(Value::Null),
+                RpcError::new(INVALID_REQUEST, "`jsonrpc` must be \"2.0\""),
+            ));
+        }
+
+        let result = match self.methods.get(&call.method) {
+            Some(handler) => handler(call.params).await,
+            None => Err(RpcError::new(
+                METHOD_NOT_FOUND,
+                format!("method `{}` not found", call.method),
+            )),
+        };
+
+        let id = call.id?;
+        Some(match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "result": result, "id": id }),
+            Err(error) => json!({ "jsonrpc": "2.0", "error": error, "id": id }),
+        })
+    }
+}
+
+fn error_response(id: Value, error: RpcError) -> Value {
+    json!({ "jsonrpc": "2.0", "error": error, "id": id })
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcCall {
+    jsonrpc: String,
+    method: String,
+    #[serde(default)]
+    params: Value,
+    /// Absent for a notification, which gets no response.
+    #[serde(default)]
+    id: Option<Value>,
+}
+
+/// The body of the [`rpc`] route's response: either the JSON-RPC response
+/// document, or nothing at all when every call in the request was a
+/// notification.
+pub enum RpcResponse {
+    Body(Value),
+    NoContent,
+}
+
+impl<'r> Responder<'r, 'static> for RpcResponse {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        match self {
+            RpcResponse::Body(value) => {
+                (rocket::http::ContentType::JSON, value.to_string()).respond_to(req)
+            }
+            RpcResponse::NoContent => rocket::http::Status::NoContent.respond_to(req),
+        }
+    }
+}
+
+/// A single Rocket route serving the registered [`RpcService`] as JSON-RPC
+/// 2.0: mount with `routes![jsonrpc::rpc]` alongside a `.manage(service)`
+/// call that provides the `RpcService`.
+#[post("/", data = "<body>")]
+pub async fn rpc(service: &State<RpcService>, body: rocket::Data<'_>) -> RpcResponse {
+    let body = match body.open(1.mebibytes()).into_string().await {
+        Ok(body) => body.into_inner(),
+        Err(err) => {
+            return RpcResponse::Body(error_response(
+                Value::Null,
+                RpcError::new(PARSE_ERROR, err.to_string()),
+            ));
+        }
+    };
+
+    let request: Value = match serde_json::from_str(&body) {
+        Ok(request) => request,
+        Err(err) => {
+            return RpcResponse::Body(error_response(
+                Value::Null,
+                RpcError::new(PARSE_ERROR, err.to_string()),
+            ));
+        }
+    };
+
+    match service.handle(request).await {
+        Some(response) => RpcResponse::Body(response),
+        None => RpcResponse::NoContent,
+    }
+}
@@ -1,25 +1,123 @@
 use chrono::{Duration, Utc};
-use cookie::{CookieBuilder, time::OffsetDateTime};
-use jsonwebtoken::{DecodingKey, EncodingKey, Header, Validation, decode, encode};
+use cookie::{time::OffsetDateTime, CookieBuilder};
+use jsonwebtoken::{
+    decode, decode_header, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation,
+};
 use once_cell::sync::OnceCell;
-use rocket::http::Cookie;
+use rocket::{
+    http::{Cookie, Status},
+    request::{self, FromRequest, Outcome},
+    Request,
+};
 use serde::{Deserialize, Serialize};
-use std::{borrow::Cow, convert::TryFrom};
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    convert::TryFrom,
+    sync::{Arc, RwLock},
+};
+use uuid::Uuid;
 
-#[derive(Default)]
+/// The `kid` a [`JWTConfig`] is registered under when none is given
+/// explicitly, and the one `sign`/`validate` fall back to.
+pub const DEFAULT_KID: &str = "default";
+
+/// Signing/verification material for one key: the algorithm, the
+/// encoding/decoding keys, and a `Validation` template applied to every
+/// `validate` call (set `aud`/`iss` checks here).
 pub struct JWTConfig {
-    secret: String,
+    algorithm: Algorithm,
+    encoding_key: EncodingKey,
+    decoding_key: DecodingKey,
+    validation: Validation,
 }
 
 impl JWTConfig {
-    pub fn set_static<'a>(secret: impl Into<Cow<'a, str>>) {
-        JWTCONFIG.get_or_init(|| Self {
-            secret: secret.into().to_string(),
-        });
+    /// An HS256 config from a shared secret.
+    pub fn from_secret(secret: impl AsRef<[u8]>) -> Self {
+        Self {
+            algorithm: Algorithm::HS256,
+            encoding_key: EncodingKey::from_secret(secret.as_ref()),
+            decoding_key: DecodingKey::from_secret(secret.as_ref()),
+            validation: Validation::new(Algorithm::HS256),
+        }
+    }
+
+    /// An RS256/RS384/RS512 (or PS*) config from PEM-encoded RSA keys.
+    pub fn from_rsa_pem(
+        algorithm: Algorithm,
+        encoding_pem: &[u8],
+        decoding_pem: &[u8],
+    ) -> anyhow::Result<Self> {
+        Ok(Self {
+            algorithm,
+            encoding_key: EncodingKey::from_rsa_pem(encoding_pem)?,
+            decoding_key: DecodingKey::from_rsa_pem(decoding_pem)?,
+            validation: Validation::new(algorithm),
+        })
+    }
+
+    /// An ES256/ES384 config from PEM-encoded EC keys.
+    pub fn from_ec_pem(
+        algorithm: Algorithm,
+        encoding_pem: &[u8],
+        decoding_pem: &[u8],
+    ) -> anyhow::Result<Self> {
+        Ok(Self {
+            algorithm,
+            encoding_key: EncodingKey::from_ec_pem(encoding_pem)?,
+            decoding_key: DecodingKey::from_ec_pem(decoding_pem)?,
+            validation: Validation::new(algorithm),
+        })
+    }
+
+    /// Override the `Validation` template used by every `validate` call
+    /// against this config, e.g. to require `aud`/`iss`.
+    pub fn with_validation(mut self, validation: Validation) -> Self {
+        self.validation = validation;
+        self
+    }
+
+    /// Register `config` under `kid`, overwriting whatever was previously
+    /// registered under that name. `validate` picks the config named by a
+    /// token's `kid` header, or `DEFAULT_KID` when the header has none. To
+    /// key-rotate, register the new key under a fresh `kid` (and sign with
+    /// [`Jwt::sign_with`]) while the old one stays registered long enough to
+    /// validate tokens already in flight.
+    pub fn set_static(kid: impl Into<String>, config: Self) {
+        JWT_CONFIGS
+            .get_or_init(|| RwLock::new(HashMap::new()))
+            .write()
+            .unwrap()
+            .insert(kid.into(), Arc::new(config));
+    }
+
+    fn get(kid: &str) -> Option<Arc<JWTConfig>> {
+        let configs = JWT_CONFIGS.get_or_init(|| RwLock::new(HashMap::new()));
+        if let Some(config) = configs.read().unwrap().get(kid) {
+            return Some(config.clone());
+        }
+        if kid == DEFAULT_KID {
+            return Some(
+                configs
+                    .write()
+                    .unwrap()
+                    .entry(DEFAULT_KID.to_string())
+                    .or_insert_with(|| Arc::new(JWTConfig::default()))
+                    .clone(),
+            );
+        }
+        None
+    }
+}
+
+impl Default for JWTConfig {
+    fn default() -> Self {
+        Self::from_secret("")
     }
 }
 
-const JWTCONFIG: OnceCell<JWTConfig> = OnceCell::new();
+static JWT_CONFIGS: OnceCell<RwLock<HashMap<String, Arc<JWTConfig>>>> = OnceCell::new();
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Jwt<T> {
@@ -48,24 +146,28 @@ where
     }
 
     pub fn validate(token: &str) -> anyhow::Result<Self> {
-        let binding = JWTCONFIG;
-        let secret = &binding.get_or_init(|| JWTConfig::default()).secret;
-        let data = decode::<Self>(
-            token,
-            &DecodingKey::from_secret(secret.as_bytes()),
-            &Validation::default(),
-        )?;
+        let kid = decode_header(token)?
+            .kid
+            .unwrap_or_else(|| DEFAULT_KID.to_string());
+        let config = JWTConfig::get(&kid)
+            .ok_or_else(|| anyhow::anyhow!("no JWTConfig registered for kid `{kid}`"))?;
+        let data = decode::<Self>(token, &config.decoding_key, &config.validation)?;
         Ok(data.claims)
     }
 
     pub fn sign(&self) -> anyhow::Result<String> {
-        let binding = JWTCONFIG;
-        let secret = &binding.get_or_init(|| JWTConfig::default()).secret;
-        Ok(encode(
-            &Header::default(),
-            &self,
-            &EncodingKey::from_secret(secret.as_bytes()),
-        )?)
+        self.sign_with(DEFAULT_KID)
+    }
+
+    /// Sign with the [`JWTConfig`] registered under a specific `kid`,
+    /// stamping that `kid` into the token header so `validate` can pick the
+    /// matching decoding key back out again.
+    pub fn sign_with(&self, kid: &str) -> anyhow::Result<String> {
+        let config = JWTConfig::get(kid)
+            .ok_or_else(|| anyhow::anyhow!("no JWTConfig registered for kid `{kid}`"))?;
+        let mut header = Header::new(config.algorithm);
+        header.kid = Some(kid.to_string());
+        Ok(encode(&header, &self, &config.encoding_key)?)
     }
 
     pub fn as_cookie<'c>(
@@ -91,3 +193,230 @@ where
         Self::validate(value.value())
     }
 }
+
+/// Where a [`Jwt<T>`] request guard looks for a token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenSource {
+    /// An `Authorization: Bearer <token>` header.
+    Header,
+    /// A named cookie (see [`JWTConfig::set_cookie_name`]).
+    Cookie,
+}
+
+#[derive(Debug, Clone)]
+struct ExtractionConfig {
+    cookie_name: String,
+    precedence: Vec<TokenSource>,
+}
+
+impl Default for ExtractionConfig {
+    fn default() -> Self {
+        Self {
+            cookie_name: "token".to_string(),
+            precedence: vec![TokenSource::Header, TokenSource::Cookie],
+        }
+    }
+}
+
+static EXTRACTION_CONFIG: OnceCell<RwLock<ExtractionConfig>> = OnceCell::new();
+
+impl JWTConfig {
+    fn extraction_config() -> &'static RwLock<ExtractionConfig> {
+        EXTRACTION_CONFIG.get_or_init(|| RwLock::new(ExtractionConfig::default()))
+    }
+
+    /// Set the cookie name the `Jwt<T>` request guard looks for. Defaults to `"token"`.
+    pub fn set_cookie_name(name: impl Into<String>) {
+        Self::extraction_config().write().unwrap().cookie_name = name.into();
+    }
+
+    /// Set the order in which the `Jwt<T>` request guard tries sources.
+    /// Defaults to header-then-cookie.
+    pub fn set_token_precedence(precedence: impl Into<Vec<TokenSource>>) {
+        Self::extraction_config().write().unwrap().precedence = precedence.into();
+    }
+}
+
+#[rocket::async_trait]
+impl<'r, T> FromRequest<'r> for Jwt<T>
+where
+    T: Serialize + for<'de> Deserialize<'de> + Send + Sync,
+{
+    type Error = anyhow::Error;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        let config = JWTConfig::extraction_config().read().unwrap().clone();
+
+        for source in &config.precedence {
+            let token = match source {
+                TokenSource::Header => req
+                    .headers()
+                    .get_one("Authorization")
+                    .and_then(|header| header.strip_prefix("Bearer "))
+                    .map(str::to_string),
+                TokenSource::Cookie => req
+                    .cookies()
+                    .get(&config.cookie_name)
+                    .map(|cookie| cookie.value().to_string()),
+            };
+
+            let Some(token) = token else { continue };
+
+            return match Self::validate(&token) {
+                Ok(jwt) if jwt.is_expired() => {
+                    Outcome::Error((Status::Unauthorized, anyhow::anyhow!("token is expired")))
+                }
+                Ok(jwt) => Outcome::Success(jwt),
+                Err(err) => Outcome::Error((Status::Unauthorized, err)),
+            };
+        }
+
+        Outcome::Error((
+            Status::Unauthorized,
+            anyhow::anyhow!("no bearer token or cookie present"),
+        ))
+    }
+}
+
+/// The claims carried by a refresh token: nothing but a unique id so the
+/// token itself stays opaque and all revocation state lives in a
+/// [`RefreshStore`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct RefreshClaims {
+    pub jti: String,
+}
+
+/// A short-lived access [`Jwt<T>`] paired with a long-lived, rotating
+/// refresh token.
+///
+/// Only the access token needs to be presented on every request; the
+/// refresh token is exchanged for a brand-new pair via [`TokenPair::refresh`]
+/// once the access token expires, without forcing the user to log in again.
+pub struct TokenPair<T> {
+    pub access: Jwt<T>,
+    pub refresh: Jwt<RefreshClaims>,
+}
+
+impl<T> TokenPair<T>
+where
+    T: Serialize + for<'de> Deserialize<'de>,
+{
+    /// Issue a new pair: an access token expiring after `access_duration`
+    /// and a refresh token carrying a fresh `jti`, expiring after
+    /// `refresh_duration`.
+    pub fn new(claims: T, access_duration: Duration, refresh_duration: Duration) -> Self {
+        let access = Jwt::new_with_exp(claims, access_duration);
+        let refresh = Jwt::new_with_exp(
+            RefreshClaims {
+                jti: Uuid::new_v4().to_string(),
+            },
+            refresh_duration,
+        );
+        Self { access, refresh }
+    }
+
+    /// Sign both halves of the pair, returning `(access_token, refresh_token)`.
+    pub fn sign(&self) -> anyhow::Result<(String, String)> {
+        Ok((self.access.sign()?, self.refresh.sign()?))
+    }
+
+    /// Validate a presented refresh token against `store` and, on success,
+    /// mint a brand-new pair while rotating the old `jti` out of the store.
+    ///
+    /// `claims` is the access-token payload to embed in the new pair (the
+    /// caller is expected to look this up for whichever subject the refresh
+    /// token's `jti` belongs to).
+    ///
+    /// The validity check and the rotation happen as a single atomic
+    /// `store.rotate` call, so two concurrent `refresh` calls presenting the
+    /// same token (a replayed/stolen token, or a client retry) can't both
+    /// succeed — only the first to rotate the `jti` wins.
+    pub fn refresh(
+        refresh_token: &str,
+        store: &dyn RefreshStore,
+        claims: T,
+        access_duration: Duration,
+        refresh_duration: Duration,
+    ) -> anyhow::Result<Self> {
+        let refresh = Jwt::<RefreshClaims>::validate(refresh_token)?;
+        if refresh.is_expired() {
+            anyhow::bail!("refresh token expired");
+        }
+
+        let pair = Self::new(claims, access_duration, refresh_duration);
+        if !store.rotate(&refresh.claims.jti, &pair.refresh.claims.jti) {
+            anyhow::bail!("refresh token has been revoked");
+        }
+        Ok(pair)
+    }
+}
+
+/// Pluggable storage for refresh-token revocation state.
+///
+/// Implementors back this with whatever they already use for persistence
+/// (a database, Redis, ...); the trait is kept object-safe so a single
+/// `&dyn RefreshStore` can be passed around or managed as Rocket state.
+pub trait RefreshStore: Send + Sync {
+    /// Whether `jti` is still a live, un-revoked refresh token.
+    fn is_valid(&self, jti: &str) -> bool;
+
+    /// Atomically check that `old_jti` is still valid and, if so, consume
+    /// it and replace it with `new_jti` in the same locked operation —
+    /// returning `true` on success. Returns `false` without registering
+    /// `new_jti` if `old_jti` was already invalid (revoked, or already
+    /// consumed by a concurrent `rotate`), so a presented token can never be
+    /// redeemed for more than one new pair.
+    fn rotate(&self, old_jti: &str, new_jti: &str) -> bool;
+
+    /// Invalidate `jti` outright, e.g. on logout.
+    fn revoke(&self, jti: &str);
+}
+
+#[cfg(feature = "memory-refresh-store")]
+pub use memory::InMemoryRefreshStore;
+
+#[cfg(feature = "memory-refresh-store")]
+mod memory {
+    use super::RefreshStore;
+    use std::{collections::HashSet, sync::Mutex};
+
+    /// A [`RefreshStore`] backed by an in-process `HashSet`.
+    ///
+    /// Simple and dependency-free, but revocation state is lost on restart
+    /// and isn't shared across processes — reach for a database-backed
+    /// `RefreshStore` impl once that matters.
+    #[derive(Default)]
+    pub struct InMemoryRefreshStore {
+        valid: Mutex<HashSet<String>>,
+    }
+
+    impl InMemoryRefreshStore {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Mark a freshly-issued `jti` as valid, e.g. right after login.
+        pub fn issue(&self, jti: impl Into<String>) {
+            self.valid.lock().unwrap().insert(jti.into());
+        }
+    }
+
+    impl RefreshStore for InMemoryRefreshStore {
+        fn is_valid(&self, jti: &str) -> bool {
+            self.valid.lock().unwrap().contains(jti)
+        }
+
+        fn rotate(&self, old_jti: &str, new_jti: &str) -> bool {
+            let mut valid = self.valid.lock().unwrap();
+            if !valid.remove(old_jti) {
+                return false;
+            }
+            valid.insert(new_jti.to_string());
+            true
+        }
+
+        fn revoke(&self, jti: &str) {
+            self.valid.lock().unwrap().remove(jti);
+        }
+    }
+}
@@ -0,0 +1,6 @@
+pub mod errors;
+pub mod helmet;
+pub mod jsonrpc;
+pub mod jwt;
+pub mod password;
+pub mod template;
@@ -0,0 +1,64 @@
+use crate::errors::{ResultJson, ResultJsonExt, ToJsonError};
+use anyhow::Result;
+use argon2::{
+    password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
+    Argon2,
+};
+use once_cell::sync::Lazy;
+use rocket::http::Status;
+
+/// Hash `password` with a fresh random salt, returning the PHC string
+/// (algorithm, params, salt and hash all in one, as `verify` expects back).
+pub fn hash(password: &str) -> Result<String> {
+    let salt = SaltString::generate(&mut OsRng);
+    let hash = Argon2::default()
+        .hash_password(password.as_bytes(), &salt)
+        .map_err(|err| anyhow::anyhow!(err))?;
+    Ok(hash.to_string())
+}
+
+/// Check `password` against a previously hashed `phc_hash`.
+pub fn verify(password: &str, phc_hash: &str) -> bool {
+    let Ok(parsed) = PasswordHash::new(phc_hash) else {
+        return false;
+    };
+    Argon2::default()
+        .verify_password(password.as_bytes(), &parsed)
+        .is_ok()
+}
+
+/// A hash of a fixed dummy password, computed once, so a missing-user
+/// lookup can still run a full `verify` and take roughly the same time as
+/// a real one.
+static DUMMY_HASH: Lazy<String> = Lazy::new(|| {
+    hash("this is not a real password, only used for timing")
+        .expect("hashing a constant password cannot fail")
+});
+
+/// Verify `password` against `phc_hash` if it's `Some`, or burn the same
+/// amount of time against a dummy hash if the user wasn't found.
+///
+/// Always returns `false` when `phc_hash` is `None`, but an attacker timing
+/// the response can't tell that apart from a wrong password on a real user
+/// — without this, the two cases complete at visibly different speeds and
+/// let an attacker enumerate valid usernames.
+pub fn verify_or_dummy(password: &str, phc_hash: Option<&str>) -> bool {
+    match phc_hash {
+        Some(phc_hash) => verify(password, phc_hash),
+        None => {
+            verify(password, &DUMMY_HASH);
+            false
+        }
+    }
+}
+
+/// Authenticate a credential, chaining a bad password into the crate's
+/// standard [`ResultJson`] failure without revealing whether the username
+/// or the password was the problem.
+pub fn authenticate(password: &str, phc_hash: Option<&str>) -> ResultJson {
+    verify_or_dummy(password, phc_hash)
+        .then_some(())
+        .json_err("invalid username or password", Status::Unauthorized)?;
+
+    ResultJson::Success("authenticated", None)
+}
@@ -1,14 +1,15 @@
 use std::sync::Arc;
 
-use arc_swap::ArcSwapOption;
-use futures::{FutureExt, future::BoxFuture};
+use arc_swap::{ArcSwap, ArcSwapOption};
+use futures::{future::BoxFuture, FutureExt};
 use rocket::{
-    Build, Data, Request, Rocket, error,
+    error,
     fairing::{self, Fairing, Info, Kind},
     http::ContentType,
     request::FromRequest,
     response::{self, Responder},
     serde::Serialize,
+    Build, Data, Request, Rocket,
 };
 
 use crate::errors::ToStatusErr;
@@ -44,15 +45,56 @@ where
     }
 }
 
+/// A `tera::Function` that forwards to a shared, reusable implementation —
+/// lets the same registered function be re-applied to a freshly rebuilt
+/// `Tera` on every `full_reload`, instead of being consumed once.
+struct SharedFunction(Arc<dyn tera::Function>);
+
+impl tera::Function for SharedFunction {
+    fn call(
+        &self,
+        args: &std::collections::HashMap<String, tera::Value>,
+    ) -> tera::Result<tera::Value> {
+        self.0.call(args)
+    }
+
+    fn is_safe(&self) -> bool {
+        self.0.is_safe()
+    }
+}
+
+/// The filter counterpart of [`SharedFunction`].
+struct SharedFilter(Arc<dyn tera::Filter>);
+
+impl tera::Filter for SharedFilter {
+    fn filter(
+        &self,
+        value: &tera::Value,
+        args: &std::collections::HashMap<String, tera::Value>,
+    ) -> tera::Result<tera::Value> {
+        self.0.filter(value, args)
+    }
+
+    fn is_safe(&self) -> bool {
+        self.0.is_safe()
+    }
+}
+
 pub struct TemplateConfig {
     registry: Vec<(Box<dyn Handler>, String)>,
     dir: String,
+    functions: Vec<(String, Arc<dyn tera::Function>)>,
+    filters: Vec<(String, Arc<dyn tera::Filter>)>,
+    full_reload: bool,
 }
 impl TemplateConfig {
     pub fn new(dir: impl AsRef<str>) -> Self {
         Self {
             registry: Vec::new(),
             dir: dir.as_ref().into(),
+            functions: Vec::new(),
+            filters: Vec::new(),
+            full_reload: cfg!(debug_assertions),
         }
     }
 
@@ -65,6 +107,57 @@ impl TemplateConfig {
             .push((Box::new(fallback), name.as_ref().into()));
         self
     }
+
+    /// Register a custom Tera function under `name`, applied to the managed
+    /// `Tera` instance on ignite (and again on every reload, see
+    /// [`TemplateConfig::full_reload`]).
+    pub fn register_function<F>(mut self, name: impl AsRef<str>, function: F) -> Self
+    where
+        F: tera::Function + 'static,
+    {
+        self.functions
+            .push((name.as_ref().into(), Arc::new(function)));
+        self
+    }
+
+    /// Register a custom Tera filter under `name`, applied the same way as
+    /// [`TemplateConfig::register_function`].
+    pub fn register_filter<F>(mut self, name: impl AsRef<str>, filter: F) -> Self
+    where
+        F: tera::Filter + 'static,
+    {
+        self.filters.push((name.as_ref().into(), Arc::new(filter)));
+        self
+    }
+
+    /// Re-parse the template glob and re-apply custom functions/filters on
+    /// every request instead of once at ignite, so template edits show up
+    /// without restarting the app. Defaults to `true` in debug builds and
+    /// `false` in release builds.
+    pub fn full_reload(mut self, enabled: bool) -> Self {
+        self.full_reload = enabled;
+        self
+    }
+
+    fn build_tera(&self) -> tera::Result<tera::Tera> {
+        let mut tera = tera::Tera::new(&self.dir)?;
+        for (name, function) in &self.functions {
+            tera.register_function(name, SharedFunction(function.clone()));
+        }
+        for (name, filter) in &self.filters {
+            tera.register_filter(name, SharedFilter(filter.clone()));
+        }
+        Ok(tera)
+    }
+
+    /// The `Tera` engine Rocket manages for this config, already carrying
+    /// any custom functions/filters — the same instance [`Template`] renders
+    /// through, for user code that wants to render outside a `Template` responder.
+    pub fn tera(req: &Request<'_>) -> Option<Arc<tera::Tera>> {
+        req.rocket()
+            .state::<ArcSwap<tera::Tera>>()
+            .map(|tera| tera.load_full())
+    }
 }
 
 #[rocket::async_trait]
@@ -77,14 +170,22 @@ impl Fairing for TemplateConfig {
     }
 
     async fn on_ignite(&self, rocket: Rocket<Build>) -> fairing::Result {
-        if let Ok(tera) = tera::Tera::new(&self.dir).inspect_err(|e| println!("{e}")) {
-            Ok(rocket.manage(tera))
-        } else {
-            Err(rocket)
+        match self.build_tera().inspect_err(|e| println!("{e}")) {
+            Ok(tera) => Ok(rocket.manage(ArcSwap::from_pointee(tera))),
+            Err(_) => Err(rocket),
         }
     }
 
     async fn on_request(&self, req: &mut Request<'_>, _data: &mut Data<'_>) {
+        if self.full_reload {
+            if let Some(state) = req.rocket().state::<ArcSwap<tera::Tera>>() {
+                match self.build_tera() {
+                    Ok(tera) => state.store(Arc::new(tera)),
+                    Err(e) => error!("template full reload failed: {e}"),
+                }
+            }
+        }
+
         let mut context = tera::Context::new();
         for (handler, name) in &self.registry {
             handler.handle(req, &mut context, name.into()).await;
@@ -112,7 +213,11 @@ impl<'r> Responder<'r, 'static> for Template {
     fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
         let context: &ArcSwapOption<tera::Context> = req.local_cache(ArcSwapOption::empty);
 
-        let tera = req.rocket().state::<tera::Tera>().status_err(500)?;
+        let tera = req
+            .rocket()
+            .state::<ArcSwap<tera::Tera>>()
+            .status_err(500)?
+            .load_full();
 
         let mut context = context
             .swap(None)